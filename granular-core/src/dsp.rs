@@ -1,15 +1,153 @@
-use std::f32::consts::PI;
+use std::f32::consts::{PI, TAU};
+use std::sync::OnceLock;
+
+// --- Fast sin/cos (table lookup) ---
+//
+// Per-sample f32::sin/cos calls are expensive, especially under WASM where
+// libm isn't backed by hardware transcendentals. A single 513-entry cosine
+// table (one extra sample so the wraparound point lines up exactly with
+// index 0) covers both sin and cos via a quarter-turn phase shift, and is
+// the basis for grain windows and LFO modulation elsewhere in this module.
+
+const COS_TABLE_SIZE: usize = 513;
+
+fn cos_table() -> &'static [f32; COS_TABLE_SIZE] {
+    static TABLE: OnceLock<[f32; COS_TABLE_SIZE]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0.0f32; COS_TABLE_SIZE];
+        for (i, entry) in table.iter_mut().enumerate() {
+            let phase = TAU * i as f32 / (COS_TABLE_SIZE - 1) as f32;
+            *entry = phase.cos();
+        }
+        table
+    })
+}
+
+pub fn fast_cos(x: f32) -> f32 {
+    let table = cos_table();
+
+    let mut phase = x % TAU;
+    if phase < 0.0 { phase += TAU; }
+
+    let pos = phase * ((COS_TABLE_SIZE - 1) as f32 / TAU);
+    let idx = pos as usize;
+    let frac = pos - idx as f32;
+    let idx_next = (idx + 1).min(COS_TABLE_SIZE - 1);
+
+    let a = table[idx];
+    let b = table[idx_next];
+    a + (b - a) * frac
+}
+
+pub fn fast_sin(x: f32) -> f32 {
+    fast_cos(x - PI / 2.0)
+}
+
+// --- RNG ---
+// Simple xorshift, fast and good enough for grain scatter / S&H LFOs.
+pub struct Rng {
+    state: u32,
+}
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        Rng { state: seed.max(1) }
+    }
+
+    pub fn next_f32(&mut self) -> f32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        (x as f32) / (u32::MAX as f32)
+    }
+}
+
+// --- LFO ---
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LfoShape {
+    Sine,
+    Triangle,
+    Saw,
+    SampleHold,
+}
+
+pub struct Lfo {
+    phase: f32, // 0..1
+    rate_hz: f32,
+    depth: f32,
+    shape: LfoShape,
+    held_value: f32,
+    rng: Rng,
+}
+
+impl Lfo {
+    pub fn new(seed: u32) -> Self {
+        Lfo {
+            phase: 0.0,
+            rate_hz: 1.0,
+            depth: 0.0,
+            shape: LfoShape::Sine,
+            held_value: 0.0,
+            rng: Rng::new(seed),
+        }
+    }
+
+    pub fn set_params(&mut self, shape: LfoShape, rate_hz: f32, depth: f32) {
+        self.shape = shape;
+        self.rate_hz = rate_hz.max(0.0);
+        self.depth = depth;
+    }
+
+    // Advances the phase by one sample and returns the scaled (-depth..depth) output.
+    pub fn tick(&mut self, sample_rate: f32) -> f32 {
+        let value = match self.shape {
+            LfoShape::Sine => fast_sin(self.phase * TAU),
+            LfoShape::Triangle => {
+                if self.phase < 0.5 { -1.0 + 4.0 * self.phase } else { 3.0 - 4.0 * self.phase }
+            }
+            LfoShape::Saw => -1.0 + 2.0 * self.phase,
+            LfoShape::SampleHold => self.held_value,
+        };
+
+        self.phase += self.rate_hz / sample_rate;
+        if self.phase >= 1.0 {
+            self.phase -= 1.0;
+            if self.shape == LfoShape::SampleHold {
+                self.held_value = self.rng.next_f32() * 2.0 - 1.0;
+            }
+        }
+
+        value * self.depth
+    }
+}
+
+// --- Biquad Filter (multimode, RBJ Audio EQ Cookbook) ---
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FilterType {
+    Lowpass,
+    Highpass,
+    Bandpass,
+    Notch,
+    Peak,
+    LowShelf,
+    HighShelf,
+}
 
-// --- Biquad Filter (Lowpass) ---
 pub struct BiquadFilter {
     sample_rate: f32,
     cutoff: f32,
     q: f32,
-    
+    filter_type: FilterType,
+    gain_db: f32,
+
     // Normalized Coefficients
     b0: f32, b1: f32, b2: f32,
     a1: f32, a2: f32,
-    
+
     // State (History)
     x1: f32, x2: f32,
     y1: f32, y2: f32,
@@ -21,6 +159,8 @@ impl BiquadFilter {
             sample_rate,
             cutoff: 2000.0,
             q: 0.707,
+            filter_type: FilterType::Lowpass,
+            gain_db: 0.0,
             b0: 0.0, b1: 0.0, b2: 0.0,
             a1: 0.0, a2: 0.0,
             x1: 0.0, x2: 0.0,
@@ -30,14 +170,20 @@ impl BiquadFilter {
         f
     }
 
-    pub fn set_params(&mut self, cutoff: f32, q: f32) {
+    pub fn set_params(&mut self, filter_type: FilterType, cutoff: f32, q: f32, gain_db: f32) {
         // Safety clamps
         let cutoff = cutoff.max(20.0).min(self.sample_rate * 0.49);
         let q = q.max(0.1).min(10.0);
-        
-        if (self.cutoff - cutoff).abs() > 0.1 || (self.q - q).abs() > 0.01 {
+
+        if self.filter_type != filter_type
+            || (self.cutoff - cutoff).abs() > 0.1
+            || (self.q - q).abs() > 0.01
+            || (self.gain_db - gain_db).abs() > 0.01
+        {
+            self.filter_type = filter_type;
             self.cutoff = cutoff;
             self.q = q;
+            self.gain_db = gain_db;
             self.calc_coeffs();
         }
     }
@@ -47,13 +193,76 @@ impl BiquadFilter {
         let alpha = w0.sin() / (2.0 * self.q);
         let cos_w0 = w0.cos();
 
-        // Lowpass coefficients (RBJ Audio EQ Cookbook)
-        let b0_raw = (1.0 - cos_w0) / 2.0;
-        let b1_raw = 1.0 - cos_w0;
-        let b2_raw = (1.0 - cos_w0) / 2.0;
-        let a0_raw = 1.0 + alpha;
-        let a1_raw = -2.0 * cos_w0;
-        let a2_raw = 1.0 - alpha;
+        // RBJ Audio EQ Cookbook, one branch per filter type
+        let (b0_raw, b1_raw, b2_raw, a0_raw, a1_raw, a2_raw) = match self.filter_type {
+            FilterType::Lowpass => (
+                (1.0 - cos_w0) / 2.0,
+                1.0 - cos_w0,
+                (1.0 - cos_w0) / 2.0,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+            FilterType::Highpass => (
+                (1.0 + cos_w0) / 2.0,
+                -(1.0 + cos_w0),
+                (1.0 + cos_w0) / 2.0,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+            FilterType::Bandpass => (
+                alpha,
+                0.0,
+                -alpha,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+            FilterType::Notch => (
+                1.0,
+                -2.0 * cos_w0,
+                1.0,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+            FilterType::Peak => {
+                let a = 10f32.powf(self.gain_db / 40.0);
+                (
+                    1.0 + alpha * a,
+                    -2.0 * cos_w0,
+                    1.0 - alpha * a,
+                    1.0 + alpha / a,
+                    -2.0 * cos_w0,
+                    1.0 - alpha / a,
+                )
+            }
+            FilterType::LowShelf => {
+                let a = 10f32.powf(self.gain_db / 40.0);
+                let beta = a.sqrt() / self.q;
+                (
+                    a * ((a + 1.0) - (a - 1.0) * cos_w0 + beta * w0.sin()),
+                    2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0),
+                    a * ((a + 1.0) - (a - 1.0) * cos_w0 - beta * w0.sin()),
+                    (a + 1.0) + (a - 1.0) * cos_w0 + beta * w0.sin(),
+                    -2.0 * ((a - 1.0) + (a + 1.0) * cos_w0),
+                    (a + 1.0) + (a - 1.0) * cos_w0 - beta * w0.sin(),
+                )
+            }
+            FilterType::HighShelf => {
+                let a = 10f32.powf(self.gain_db / 40.0);
+                let beta = a.sqrt() / self.q;
+                (
+                    a * ((a + 1.0) + (a - 1.0) * cos_w0 + beta * w0.sin()),
+                    -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0),
+                    a * ((a + 1.0) + (a - 1.0) * cos_w0 - beta * w0.sin()),
+                    (a + 1.0) - (a - 1.0) * cos_w0 + beta * w0.sin(),
+                    2.0 * ((a - 1.0) - (a + 1.0) * cos_w0),
+                    (a + 1.0) - (a - 1.0) * cos_w0 - beta * w0.sin(),
+                )
+            }
+        };
 
         // Normalize by a0
         let inv_a0 = 1.0 / a0_raw;
@@ -117,22 +326,28 @@ impl DelayLine {
     // Reads from delay line at 'delay_ms' in the past
     pub fn read(&self, delay_ms: f32) -> f32 {
         let delay_samples = (delay_ms / 1000.0 * self.sample_rate).max(0.0);
-        let read_ptr_raw = self.write_pos as f32 - delay_samples;
-        
+        self.read_samples(delay_samples)
+    }
+
+    // Reads from delay line at a fractional number of samples in the past.
+    // Used where the read offset itself needs to be modulated (e.g. chorused allpasses).
+    pub fn read_samples(&self, delay_samples: f32) -> f32 {
+        let read_ptr_raw = self.write_pos as f32 - delay_samples.max(0.0);
+
         // Wrap logic handled by using modulo on integer parts
         let len_f = self.buffer.len() as f32;
         let mut ptr = read_ptr_raw;
         while ptr < 0.0 { ptr += len_f; }
         while ptr >= len_f { ptr -= len_f; }
-        
+
         let idx_int = ptr.floor() as usize;
         let frac = ptr - idx_int as f32;
-        
+
         let idx_next = (idx_int + 1) % self.buffer.len();
-        
+
         let s1 = self.buffer[idx_int];
         let s2 = self.buffer[idx_next];
-        
+
         // Linear interpolation
         s1 + (s2 - s1) * frac
     }
@@ -160,140 +375,312 @@ impl DelayLine {
 
 // --- Reverb Primitives ---
 
-struct Comb {
+struct Allpass {
     delay: DelayLine,
     feedback: f32,
-    filter_store: f32,
-    damp: f32,
 }
 
-impl Comb {
+impl Allpass {
     fn new(size: usize, sample_rate: f32) -> Self {
-        Comb {
+        Allpass {
             delay: DelayLine::new_samples(size, sample_rate),
             feedback: 0.5,
-            filter_store: 0.0,
-            damp: 0.2,
         }
     }
-    
+
+    fn new_with_feedback(size: usize, sample_rate: f32, feedback: f32) -> Self {
+        Allpass {
+            delay: DelayLine::new_samples(size, sample_rate),
+            feedback,
+        }
+    }
+
+    // One-multiplier Schroeder allpass: v[n] = x[n] + g*v[n-D], y[n] = v[n-D] - g*v[n].
+    // Unity gain for any |g| < 1 (the delay line stores v, not x, so the output
+    // must subtract g times the value just written, not g times the raw input).
     fn process(&mut self, input: f32) -> f32 {
-        let output = self.delay.read_at(self.delay.buffer.len() - 1);
-        
-        self.filter_store = output * (1.0 - self.damp) + self.filter_store * self.damp;
-        
-        let to_delay = input + self.filter_store * self.feedback;
+        let delayed = self.delay.read_at(self.delay.buffer.len() - 1);
+        let to_delay = input + (delayed * self.feedback);
         self.delay.write(to_delay);
-        
-        output
+
+        delayed - (self.feedback * to_delay)
     }
-    
-    fn set_feedback(&mut self, val: f32) { self.feedback = val; }
-    fn set_damp(&mut self, val: f32) { self.damp = val; }
 }
 
-struct Allpass {
+// --- Plate Reverb (Dattorro, 1997 figure-eight tank) ---
+//
+// Replaces the old Freeverb (parallel comb + series allpass) mono reverb with
+// Jon Dattorro's plate topology: input diffusion into a cross-coupled tank of
+// two modulated-allpass/delay/damping chains, read out via fixed taps.
+// Tunings below are scaled from the reference rate used in Dattorro's paper.
+//
+// The L/R combination below is a simplified 3-tap-per-channel mix (left: tank
+// B's long and output taps minus tank A's output tap; right: the mirror
+// image), built from one long-delay tap and one output-delay tap per tank
+// half, not Dattorro's full
+// six-tap weighted accumulator table — that table pulls taps from specific
+// offsets inside the input diffusers and both tanks' delay lines, which this
+// simpler two-delay-per-half tank doesn't expose. Close enough in spirit for
+// a lush stereo field, not a literal transcription of the reference design.
+
+const PLATE_REFERENCE_SR: f32 = 29761.0;
+const INPUT_DIFFUSION_TUNING: [usize; 4] = [142, 107, 379, 277];
+const INPUT_DIFFUSION_FEEDBACK: [f32; 4] = [0.75, 0.75, 0.625, 0.625];
+
+// Low-rate LFO driving the modulated allpasses (chorusing in the tank).
+const TANK_MOD_RATE_HZ: f32 = 0.6;
+// Headroom so base +/- depth never clips the buffer, at the reference rate;
+// scaled by sr_scale wherever it sizes a buffer, same as every other tuning
+// constant in this file.
+const TANK_MOD_MARGIN_SAMPLES: f32 = 16.0;
+
+struct ModulatedAllpass {
     delay: DelayLine,
-    feedback: f32,
 }
 
-impl Allpass {
-    fn new(size: usize, sample_rate: f32) -> Self {
-        Allpass {
-            delay: DelayLine::new_samples(size, sample_rate),
-            feedback: 0.5,
+impl ModulatedAllpass {
+    fn new(max_delay_samples: usize, sample_rate: f32) -> Self {
+        ModulatedAllpass {
+            delay: DelayLine::new_samples(max_delay_samples, sample_rate),
         }
     }
-    
-    fn process(&mut self, input: f32) -> f32 {
-        let buffered_val = self.delay.read_at(self.delay.buffer.len() - 1);
-        let to_delay = input + (buffered_val * self.feedback);
+
+    // Same one-multiplier Schroeder allpass topology as `Allpass::process`, but
+    // the delay length is supplied per-sample (fractional, interpolated)
+    // instead of fixed.
+    fn process(&mut self, input: f32, delay_samples: f32, feedback: f32) -> f32 {
+        let delayed = self.delay.read_samples(delay_samples);
+        let to_delay = input + delayed * feedback;
         self.delay.write(to_delay);
-        
-        // Output = -input + buffered
-        // Standard Schroder Allpass: y[n] = -g * x[n] + x[n-D] + g * y[n-D]
-        // Implementation here: 
-        // buf = x[n-D] + g * y[n-D] (stored)
-        // out = -x[n] + buf(stored) ?? 
-        // Let's stick to Freeverb form:
-        // output = buffered - input 
-        // buffer_input = input + buffered * feedback
-        
-        buffered_val - input
+        delayed - (feedback * to_delay)
     }
 }
 
-// --- Freeverb Implementation ---
-// Tunings from Freeverb
-const FIXED_GAIN: f32 = 0.015;
-const SCALE_WET: f32 = 3.0;
-const SCALE_DRY: f32 = 2.0;
-const SCALE_DAMP: f32 = 0.4;
-const SCALE_ROOM: f32 = 0.28;
-const OFFSET_ROOM: f32 = 0.7;
-
-// Stereo spread not implemented, mono version here
-const COMB_TUNING_L: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
-const ALLPASS_TUNING_L: [usize; 4] = [556, 441, 341, 225];
-
-pub struct Reverb {
-    combs: Vec<Comb>,
-    allpasses: Vec<Allpass>,
-    mix: f32, // 0..1
+// One half of the figure-eight tank: modulated allpass -> long delay -> damping
+// lowpass -> fixed allpass -> long delay, with taps read back for the mix.
+struct TankHalf {
+    mod_allpass: ModulatedAllpass,
+    mod_base_samples: f32,
+    mod_feedback: f32,
+    long_delay: DelayLine,
+    long_tap: usize,
+    damp_state: f32,
+    fixed_allpass: Allpass,
+    out_delay: DelayLine,
+    out_tap: usize,
 }
 
-impl Reverb {
-    pub fn new(sample_rate: f32) -> Self {
-        // Scale tunings by sample rate (original is 44100)
-        let sr_scale = sample_rate / 44100.0;
-        
-        let mut combs = Vec::new();
-        for t in COMB_TUNING_L.iter() {
-            combs.push(Comb::new((*t as f32 * sr_scale) as usize, sample_rate));
-        }
-        
-        let mut allpasses = Vec::new();
-        for t in ALLPASS_TUNING_L.iter() {
-            allpasses.push(Allpass::new((*t as f32 * sr_scale) as usize, sample_rate));
+impl TankHalf {
+    fn new(
+        mod_base: usize,
+        long_delay_size: usize,
+        fixed_ap_size: usize,
+        out_delay_size: usize,
+        sample_rate: f32,
+    ) -> Self {
+        let sr_scale = sample_rate / PLATE_REFERENCE_SR;
+        let mod_margin = (TANK_MOD_MARGIN_SAMPLES * sr_scale) as usize;
+        let mod_capacity = mod_base + mod_margin * 2;
+        TankHalf {
+            mod_allpass: ModulatedAllpass::new(mod_capacity, sample_rate),
+            mod_base_samples: mod_base as f32,
+            mod_feedback: 0.7,
+            long_delay: DelayLine::new_samples(long_delay_size, sample_rate),
+            long_tap: long_delay_size / 3,
+            damp_state: 0.0,
+            fixed_allpass: Allpass::new_with_feedback(fixed_ap_size, sample_rate, 0.5),
+            out_delay: DelayLine::new_samples(out_delay_size, sample_rate),
+            out_tap: out_delay_size / 2,
         }
-        
-        Reverb {
-            combs,
-            allpasses,
+    }
+
+    fn process(&mut self, input: f32, lfo: f32, mod_depth_samples: f32, damping: f32) -> f32 {
+        let delay_samples = (self.mod_base_samples + lfo * mod_depth_samples).max(1.0);
+        let diffused = self.mod_allpass.process(input, delay_samples, self.mod_feedback);
+
+        let delayed = self.long_delay.read_at(self.long_delay.buffer.len() - 1);
+        self.long_delay.write(diffused);
+
+        self.damp_state = delayed * (1.0 - damping) + self.damp_state * damping;
+
+        let ap_out = self.fixed_allpass.process(self.damp_state);
+
+        let out = self.out_delay.read_at(self.out_delay.buffer.len() - 1);
+        self.out_delay.write(ap_out);
+        out
+    }
+
+    fn tap(&self) -> (f32, f32) {
+        (self.long_delay.read_at(self.long_tap), self.out_delay.read_at(self.out_tap))
+    }
+}
+
+pub struct PlateReverb {
+    mix: f32,     // 0..1
+    decay: f32,   // 0..1, tank feedback amount
+    damping: f32, // 0..1
+
+    input_lpf_state: f32,
+    input_lpf_coeff: f32,
+
+    pre_delay: DelayLine,
+    pre_delay_ms: f32,
+
+    diffuser1: Allpass,
+    diffuser2: Allpass,
+    diffuser3: Allpass,
+    diffuser4: Allpass,
+
+    tank_a: TankHalf,
+    tank_b: TankHalf,
+
+    lfo_phase: f32,
+    mod_depth: f32,
+    sr_scale: f32, // sample_rate / PLATE_REFERENCE_SR, applied to mod_depth in set_params
+
+    tail_a: f32,
+    tail_b: f32,
+
+    // Freeverb-style "stereospread": the right channel's tank taps are read
+    // a fixed number of samples later than the left, decorrelating L/R.
+    stereo_spread_r: DelayLine,
+    stereo_spread_samples: f32,
+}
+
+// Freeverb's stereospread constant, at its reference sample rate of 44100 Hz.
+const STEREO_SPREAD_SAMPLES: f32 = 23.0;
+
+impl PlateReverb {
+    pub fn new(sample_rate: f32) -> Self {
+        let sr_scale = sample_rate / PLATE_REFERENCE_SR;
+        let scaled = |t: usize| (t as f32 * sr_scale) as usize;
+        let stereo_spread_samples = STEREO_SPREAD_SAMPLES * (sample_rate / 44100.0);
+
+        PlateReverb {
             mix: 0.0,
+            decay: 0.5,
+            damping: 0.5,
+
+            input_lpf_state: 0.0,
+            input_lpf_coeff: 0.9995,
+
+            pre_delay: DelayLine::new(500.0, sample_rate),
+            pre_delay_ms: 0.0,
+
+            diffuser1: Allpass::new_with_feedback(scaled(INPUT_DIFFUSION_TUNING[0]), sample_rate, INPUT_DIFFUSION_FEEDBACK[0]),
+            diffuser2: Allpass::new_with_feedback(scaled(INPUT_DIFFUSION_TUNING[1]), sample_rate, INPUT_DIFFUSION_FEEDBACK[1]),
+            diffuser3: Allpass::new_with_feedback(scaled(INPUT_DIFFUSION_TUNING[2]), sample_rate, INPUT_DIFFUSION_FEEDBACK[2]),
+            diffuser4: Allpass::new_with_feedback(scaled(INPUT_DIFFUSION_TUNING[3]), sample_rate, INPUT_DIFFUSION_FEEDBACK[3]),
+
+            tank_a: TankHalf::new(scaled(672), scaled(4453), scaled(1800), scaled(3720), sample_rate),
+            tank_b: TankHalf::new(scaled(908), scaled(4217), scaled(2656), scaled(3163), sample_rate),
+
+            lfo_phase: 0.0,
+            mod_depth: 8.0 * sr_scale,
+            sr_scale,
+
+            tail_a: 0.0,
+            tail_b: 0.0,
+
+            stereo_spread_r: DelayLine::new_samples(stereo_spread_samples as usize + 8, sample_rate),
+            stereo_spread_samples,
         }
     }
-    
-    pub fn set_params(&mut self, mix: f32, room_size: f32, damp: f32) {
+
+    // `mod_depth` is expressed in samples at the reference rate (matching the
+    // tank tunings above) and scaled to the instance's actual sample rate here,
+    // so callers don't need to know about `PLATE_REFERENCE_SR`.
+    pub fn set_params(&mut self, mix: f32, decay: f32, damping: f32, pre_delay_ms: f32, mod_depth: f32) {
         self.mix = mix.clamp(0.0, 1.0);
-        let feedback = room_size * SCALE_ROOM + OFFSET_ROOM;
-        let d = damp * SCALE_DAMP;
-        
-        for c in self.combs.iter_mut() {
-            c.set_feedback(feedback);
-            c.set_damp(d);
-        }
+        self.decay = decay.clamp(0.0, 0.999);
+        self.damping = damping.clamp(0.0, 0.999);
+        self.pre_delay_ms = pre_delay_ms.max(0.0).min(500.0);
+        self.mod_depth = mod_depth.max(0.0) * self.sr_scale;
     }
-    
-    pub fn process(&mut self, input: f32) -> f32 {
+
+    // Returns (left, right). Both channels are driven by the same mono input;
+    // stereo width comes from the tank's cross-coupled taps plus the
+    // stereo-spread delay applied to the right channel's wet signal.
+    pub fn process(&mut self, input: f32) -> (f32, f32) {
         if self.mix <= 0.001 {
-            return input;
+            return (input, input);
         }
-        
-        let input_scaled = input * FIXED_GAIN;
-        let mut out = 0.0;
-        
-        // Parallel Combs
-        for c in self.combs.iter_mut() {
-            out += c.process(input_scaled);
+
+        let delayed = self.pre_delay.read(self.pre_delay_ms);
+        self.pre_delay.write(input);
+
+        self.input_lpf_state = delayed * (1.0 - self.input_lpf_coeff) + self.input_lpf_state * self.input_lpf_coeff;
+        let band_limited = self.input_lpf_state;
+
+        let mut diffused = self.diffuser1.process(band_limited);
+        diffused = self.diffuser2.process(diffused);
+        diffused = self.diffuser3.process(diffused);
+        diffused = self.diffuser4.process(diffused);
+
+        self.lfo_phase += 2.0 * PI * TANK_MOD_RATE_HZ / self.pre_delay.sample_rate;
+        if self.lfo_phase >= 2.0 * PI { self.lfo_phase -= 2.0 * PI; }
+        let lfo_a = fast_sin(self.lfo_phase);
+        let lfo_b = fast_sin(self.lfo_phase + PI);
+
+        let feed_a = diffused + self.tail_b * self.decay;
+        let feed_b = diffused + self.tail_a * self.decay;
+
+        self.tail_a = self.tank_a.process(feed_a, lfo_a, self.mod_depth, self.damping);
+        self.tail_b = self.tank_b.process(feed_b, lfo_b, self.mod_depth, self.damping);
+
+        // Simplified 3-tap accumulator (see module comment): not Dattorro's
+        // full six-tap table, just one long/out tap per tank half, cross-read
+        // so each output channel favors the opposite half for stereo width.
+        let (a_long, a_out) = self.tank_a.tap();
+        let (b_long, b_out) = self.tank_b.tap();
+
+        let wet_left = b_long + b_out - a_out;
+        let wet_right_raw = a_long + a_out - b_out;
+
+        self.stereo_spread_r.write(wet_right_raw);
+        let wet_right = self.stereo_spread_r.read_samples(self.stereo_spread_samples);
+
+        (
+            input * (1.0 - self.mix) + wet_left * self.mix,
+            input * (1.0 - self.mix) + wet_right * self.mix,
+        )
+    }
+
+    // Convenience for callers that only want a single summed channel.
+    pub fn process_mono(&mut self, input: f32) -> f32 {
+        let (l, r) = self.process(input);
+        (l + r) * 0.5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Regression test for a tank-gain bug where a non-unity allpass stage
+    // compounded every round trip through the decay loop into a runaway tail
+    // instead of decaying. Feeds a loud burst, then silence, and asserts the
+    // tail has died down to near-silence well before the end of the run.
+    #[test]
+    fn plate_reverb_tail_decays_under_sustained_silence() {
+        let mut reverb = PlateReverb::new(44100.0);
+        reverb.set_params(1.0, 0.9, 0.3, 0.0, 8.0);
+
+        for _ in 0..1000 {
+            reverb.process_mono(1.0);
         }
-        
-        // Series Allpasses
-        for a in self.allpasses.iter_mut() {
-            out = a.process(out);
+        for _ in 0..199_000 {
+            reverb.process_mono(0.0);
         }
-        
-        // Mix
-        input * (1.0 - self.mix) + out * self.mix * SCALE_WET
+
+        let window = 1000;
+        let mut sum_sq = 0.0;
+        for _ in 0..window {
+            let s = reverb.process_mono(0.0);
+            sum_sq += s * s;
+        }
+        let rms = (sum_sq / window as f32).sqrt();
+
+        assert!(rms < 0.01, "reverb tail failed to decay: rms={rms}");
     }
 }
@@ -1,51 +1,174 @@
 use wasm_bindgen::prelude::*;
 
 mod dsp;
-use dsp::{BiquadFilter, DelayLine, Reverb};
+use dsp::{fast_cos, fast_sin, BiquadFilter, DelayLine, FilterType, Lfo, LfoShape, PlateReverb, Rng};
+use std::f32::consts::PI;
 
-// Simple Xorshift RNG
-struct Rng {
-    state: u32,
+const LFO_COUNT: usize = 4;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LfoTarget {
+    None,
+    FilterCutoff,
+    DelayTimeMs,
+    GrainPitch,
+}
+
+fn lfo_target_from_u32(val: u32) -> LfoTarget {
+    match val {
+        1 => LfoTarget::FilterCutoff,
+        2 => LfoTarget::DelayTimeMs,
+        3 => LfoTarget::GrainPitch,
+        _ => LfoTarget::None,
+    }
+}
+
+fn lfo_shape_from_u32(val: u32) -> LfoShape {
+    match val {
+        1 => LfoShape::Triangle,
+        2 => LfoShape::Saw,
+        3 => LfoShape::SampleHold,
+        _ => LfoShape::Sine,
+    }
+}
+
+// Tukey taper width, as a fraction of grain length, for each cosine-tapered edge.
+const TUKEY_TAPER: f32 = 0.25;
+// Gaussian window std-dev, as a fraction of grain length.
+const GAUSSIAN_SIGMA: f32 = 0.4;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum WindowType {
+    Trapezoid,
+    Hann,
+    Tukey,
+    Gaussian,
+    BlackmanHarris,
 }
 
-impl Rng {
-    fn new(seed: u32) -> Self {
-        Rng { state: seed.max(1) }
+fn window_type_from_u32(val: u32) -> WindowType {
+    match val {
+        1 => WindowType::Hann,
+        2 => WindowType::Tukey,
+        3 => WindowType::Gaussian,
+        4 => WindowType::BlackmanHarris,
+        _ => WindowType::Trapezoid,
+    }
+}
+
+// Grain amplitude envelope at normalized position `p` (0..1 across the grain).
+fn grain_window(window_type: WindowType, p: f32, grain_length: f32) -> f32 {
+    match window_type {
+        WindowType::Trapezoid => {
+            // Original crude attack/release, derived from the grain's own length.
+            let attack = 0.2f32.min(10.0 / grain_length);
+            let release = 0.25f32.min(12.0 / grain_length);
+
+            if p < attack {
+                p / attack.max(1e-6)
+            } else if p > 1.0 - release {
+                (1.0 - p) / release.max(1e-6)
+            } else {
+                1.0
+            }
+        }
+        WindowType::Hann => 0.5 * (1.0 - fast_cos(2.0 * PI * p)),
+        WindowType::Tukey => {
+            let r = TUKEY_TAPER;
+            if p < r {
+                0.5 * (1.0 + fast_cos(PI * (p / r - 1.0)))
+            } else if p > 1.0 - r {
+                0.5 * (1.0 + fast_cos(PI * ((1.0 - p) / r - 1.0)))
+            } else {
+                1.0
+            }
+        }
+        WindowType::Gaussian => {
+            let t = (p - 0.5) / GAUSSIAN_SIGMA;
+            (-0.5 * t * t).exp()
+        }
+        WindowType::BlackmanHarris => {
+            const A0: f32 = 0.35875;
+            const A1: f32 = 0.48829;
+            const A2: f32 = 0.14128;
+            const A3: f32 = 0.01168;
+            A0 - A1 * fast_cos(2.0 * PI * p) + A2 * fast_cos(4.0 * PI * p) - A3 * fast_cos(6.0 * PI * p)
+        }
     }
+}
 
-    fn next_f32(&mut self) -> f32 {
-        let mut x = self.state;
-        x ^= x << 13;
-        x ^= x >> 17;
-        x ^= x << 5;
-        self.state = x;
-        (x as f32) / (u32::MAX as f32)
+// Maps the numeric filter mode sent from JS onto `dsp::FilterType`.
+fn filter_type_from_u32(val: u32) -> FilterType {
+    match val {
+        1 => FilterType::Highpass,
+        2 => FilterType::Bandpass,
+        3 => FilterType::Notch,
+        4 => FilterType::Peak,
+        5 => FilterType::LowShelf,
+        6 => FilterType::HighShelf,
+        _ => FilterType::Lowpass,
     }
 }
 
 // Struttura Grain interna
 struct Grain {
-    start_sample: f32, 
-    end_sample: f32,   
-    length: f32,       
-    age: f32,          
-    rate: f32,         
-    amp: f32,          
+    start_sample: f32,
+    end_sample: f32,
+    length: f32,
+    age: f32,
+    pitch_rate: f32, // within-grain read speed; independent of the engine's scan_rate
+    amp: f32,
+    pan: f32, // -1 (left) .. 1 (right), randomized per spawn
 }
 
 impl Grain {
-    fn new(start: f32, length: f32, rate: f32) -> Self {
+    fn new(start: f32, length: f32, pitch_rate: f32, pan: f32) -> Self {
         Grain {
             start_sample: start,
-            end_sample: start + (length * rate), 
+            end_sample: start + (length * pitch_rate),
             length,
             age: 0.0,
-            rate,
+            pitch_rate,
             amp: 1.0,
+            pan,
         }
     }
 }
 
+// 4-point cubic Hermite interpolation through (y0, y1, y2, y3) at fractional
+// position `frac` between y1 and y2. Lower aliasing than linear interpolation
+// when a grain's pitch_rate pushes the read pointer well above 1x.
+fn cubic_hermite(y0: f32, y1: f32, y2: f32, y3: f32, frac: f32) -> f32 {
+    let c0 = y1;
+    let c1 = 0.5 * (y2 - y0);
+    let c2 = y0 - 2.5 * y1 + 2.0 * y2 - 0.5 * y3;
+    let c3 = 0.5 * (y3 - y0) + 1.5 * (y1 - y2);
+    ((c3 * frac + c2) * frac + c1) * frac + c0
+}
+
+// Reads the audio buffer at a fractional sample position, linear or cubic Hermite.
+fn read_buffer_interpolated(buffer: &[f32], pos_int: usize, frac: f32, hq: bool) -> f32 {
+    if pos_int >= buffer.len() - 1 {
+        return 0.0;
+    }
+    if hq && buffer.len() >= 4 {
+        let i0 = pos_int.saturating_sub(1);
+        let i2 = (pos_int + 1).min(buffer.len() - 1);
+        let i3 = (pos_int + 2).min(buffer.len() - 1);
+        cubic_hermite(buffer[i0], buffer[pos_int], buffer[i2], buffer[i3], frac)
+    } else {
+        let s1 = buffer[pos_int];
+        let s2 = buffer[pos_int + 1];
+        s1 + (s2 - s1) * frac
+    }
+}
+
+// Equal-power pan gains for a grain's `pan` (-1..1) via the fast cos/sin table.
+fn equal_power_gains(pan: f32) -> (f32, f32) {
+    let theta = (pan.clamp(-1.0, 1.0) + 1.0) * 0.25 * PI; // 0..PI/2
+    (fast_cos(theta), fast_sin(theta))
+}
+
 // Helper per allocare memoria da JS
 #[wasm_bindgen]
 pub fn alloc(len: usize) -> *mut f32 {
@@ -66,24 +189,40 @@ pub struct GranularEngine {
     density: f32,
     random_start_ms: f32,
     pitch_semitones: f32,
-    
+    window_type: WindowType,
+    stereo_width: f32,
+    scan_rate: f32, // buffer traversal speed, independent of grain pitch_rate (time-stretch)
+    hq_interpolation: bool, // 4-point cubic Hermite vs. plain linear grain reads
+
     // Effects
-    filter: BiquadFilter,
-    delay: DelayLine,
-    reverb: Reverb,
+    filter_l: BiquadFilter,
+    filter_r: BiquadFilter,
+    delay_l: DelayLine,
+    delay_r: DelayLine,
+    reverb: PlateReverb,
     
     // Effect Params
+    filter_type: FilterType,
+    base_cutoff: f32,
+    base_q: f32,
+    base_filter_gain: f32,
     delay_mix: f32,
     delay_feedback: f32,
     delay_time_ms: f32,
-    
+
     reverb_mix: f32,
     master_gain: f32,
-    
+
+    // Modulation
+    lfos: Vec<Lfo>,
+    lfo_targets: Vec<LfoTarget>,
+    lfo_pitch_mod: f32, // latest grain-pitch LFO sum, sampled by spawn_grain
+
     // State
     time_since_last_grain: f32,
     region_start: usize,
     region_end: usize,
+    scan_position: f32, // read-pointer position in samples, advanced by scan_rate each sample
     is_playing: bool,
     rng: Rng,
 }
@@ -101,21 +240,36 @@ impl GranularEngine {
             density: 15.0,
             random_start_ms: 40.0,
             pitch_semitones: 0.0,
+            window_type: WindowType::Trapezoid,
+            stereo_width: 0.0,
+            scan_rate: 1.0,
+            hq_interpolation: false,
+
+            filter_l: BiquadFilter::new(sample_rate),
+            filter_r: BiquadFilter::new(sample_rate),
+            delay_l: DelayLine::new(2000.0, sample_rate), // 2s max delay
+            delay_r: DelayLine::new(2000.0, sample_rate),
+            reverb: PlateReverb::new(sample_rate),
             
-            filter: BiquadFilter::new(sample_rate),
-            delay: DelayLine::new(2000.0, sample_rate), // 2s max delay
-            reverb: Reverb::new(sample_rate),
-            
+            filter_type: FilterType::Lowpass,
+            base_cutoff: 2000.0,
+            base_q: 0.707,
+            base_filter_gain: 0.0,
             delay_mix: 0.0,
             delay_feedback: 0.3,
             delay_time_ms: 250.0,
-            
+
             reverb_mix: 0.0,
             master_gain: 1.0,
-            
+
+            lfos: (0..LFO_COUNT).map(|i| Lfo::new(7919 + i as u32 * 104729)).collect(),
+            lfo_targets: vec![LfoTarget::None; LFO_COUNT],
+            lfo_pitch_mod: 0.0,
+
             time_since_last_grain: 0.0,
             region_start: 0,
             region_end: 0,
+            scan_position: 0.0,
             is_playing: false,
             rng: Rng::new(12345),
         }
@@ -127,6 +281,7 @@ impl GranularEngine {
         self.audio_buffer = slice.to_vec();
         self.region_start = 0;
         self.region_end = self.audio_buffer.len();
+        self.scan_position = self.region_start as f32;
     }
 
     fn set_region_internal(&mut self, start: usize, end: usize) {
@@ -134,30 +289,70 @@ impl GranularEngine {
         if len == 0 { return; }
         self.region_start = start.min(len);
         self.region_end = end.min(len).max(self.region_start);
+        self.scan_position = self.region_start as f32;
     }
     
-    fn set_params_internal(&mut self, grain_size_ms: f32, density: f32, random_start_ms: f32, pitch_semitones: f32) {
+    fn set_window_type_internal(&mut self, window_type: u32) {
+        self.window_type = window_type_from_u32(window_type);
+    }
+
+    fn set_params_internal(&mut self, grain_size_ms: f32, density: f32, random_start_ms: f32, pitch_semitones: f32, stereo_width: f32, scan_rate: f32, hq_interpolation: bool) {
         self.grain_size_ms = grain_size_ms;
         self.density = density;
         self.random_start_ms = random_start_ms;
         self.pitch_semitones = pitch_semitones;
+        self.stereo_width = stereo_width.clamp(0.0, 1.0);
+        self.scan_rate = scan_rate;
+        self.hq_interpolation = hq_interpolation;
     }
     
-    fn set_effect_params_internal(&mut self, cutoff: f32, q: f32, delay_time_ms: f32, delay_feedback: f32, delay_mix: f32, reverb_mix: f32, master_gain: f32) {
-        self.filter.set_params(cutoff, q);
+    fn set_effect_params_internal(&mut self, filter_type: u32, cutoff: f32, q: f32, filter_gain: f32, delay_time_ms: f32, delay_feedback: f32, delay_mix: f32, reverb_mix: f32, master_gain: f32) {
+        self.filter_type = filter_type_from_u32(filter_type);
+        self.base_cutoff = cutoff;
+        self.base_q = q;
+        self.base_filter_gain = filter_gain;
+        self.filter_l.set_params(self.filter_type, cutoff, q, filter_gain);
+        self.filter_r.set_params(self.filter_type, cutoff, q, filter_gain);
         self.delay_time_ms = delay_time_ms;
         self.delay_feedback = delay_feedback;
         self.delay_mix = delay_mix.clamp(0.0, 1.0);
-        
+
         self.reverb_mix = reverb_mix.clamp(0.0, 1.0);
-        self.reverb.set_params(self.reverb_mix, 0.5, 0.5); // Default room/damp
-        
+        self.reverb.set_params(self.reverb_mix, 0.5, 0.5, 0.0, 8.0); // Default decay/damp, no pre-delay
+
         self.master_gain = master_gain.max(0.0);
     }
-    
+
+    fn set_lfo_internal(&mut self, index: usize, target: u32, shape: u32, rate_hz: f32, depth: f32) {
+        if let (Some(lfo), Some(t)) = (self.lfos.get_mut(index), self.lfo_targets.get_mut(index)) {
+            lfo.set_params(lfo_shape_from_u32(shape), rate_hz, depth);
+            *t = lfo_target_from_u32(target);
+        }
+    }
+
+    // Ticks every LFO once and returns the summed modulation for each routable
+    // target: (filter cutoff in octaves, delay time in ms, grain pitch in semitones).
+    fn tick_lfos(&mut self) -> (f32, f32, f32) {
+        let mut cutoff_mod = 0.0;
+        let mut delay_mod = 0.0;
+        let mut pitch_mod = 0.0;
+
+        for (lfo, target) in self.lfos.iter_mut().zip(self.lfo_targets.iter()) {
+            let value = lfo.tick(self.sample_rate);
+            match target {
+                LfoTarget::FilterCutoff => cutoff_mod += value,
+                LfoTarget::DelayTimeMs => delay_mod += value,
+                LfoTarget::GrainPitch => pitch_mod += value,
+                LfoTarget::None => {}
+            }
+        }
+
+        (cutoff_mod, delay_mod, pitch_mod)
+    }
+
     fn set_playing_internal(&mut self, playing: bool) {
         self.is_playing = playing;
-        // Reset effects state on stop? Or keep ringing? 
+        // Reset effects state on stop? Or keep ringing?
         // Keeping ringing is usually nicer.
     }
 
@@ -170,7 +365,6 @@ impl GranularEngine {
         // Pre-fetch constants to avoid struct lookup in tight loop
         let delay_mix = self.delay_mix;
         let delay_fb = self.delay_feedback;
-        let delay_time = self.delay_time_ms;
         let master_gain = self.master_gain;
 
         // Se non sta suonando e il buffer non è vuoto, output silenzio (o coda riverbero?)
@@ -187,7 +381,15 @@ impl GranularEngine {
         
         for i in 0..len {
             let mut current_sample = 0.0;
-            
+
+            // 0. Modulation: advance every LFO and route its output to its target
+            let (cutoff_mod, delay_mod, pitch_mod) = self.tick_lfos();
+            self.lfo_pitch_mod = pitch_mod;
+
+            let modulated_cutoff = self.base_cutoff * 2f32.powf(cutoff_mod);
+            self.filter_l.set_params(self.filter_type, modulated_cutoff, self.base_q, self.base_filter_gain);
+            let delay_time = (self.delay_time_ms + delay_mod).max(0.0);
+
             // 1. Granular Generation
             if !self.audio_buffer.is_empty() && self.is_playing {
                 self.time_since_last_grain += 1.0;
@@ -195,43 +397,27 @@ impl GranularEngine {
                     self.spawn_grain();
                     self.time_since_last_grain -= interval_samples;
                 }
+                self.advance_scan_position();
             }
 
             // Process active grains (sempre, anche se playing stoppato, per far finire i grani correnti)
             if !self.grains.is_empty() {
+                let hq = self.hq_interpolation;
                 let mut j = 0;
                 while j < self.grains.len() {
                     let remove = {
                         let g = &mut self.grains[j];
-                        
+
                         let env_pos = g.age / g.length;
-                        // Simple trapezoidal window
-                        let attack = 0.2f32.min(10.0 / g.length);
-                        let release = 0.25f32.min(12.0 / g.length);
-                        
-                        let mut amp = 0.0;
-                        if env_pos < attack {
-                            amp = env_pos / attack.max(1e-6);
-                        } else if env_pos > 1.0 - release {
-                            amp = (1.0 - env_pos) / release.max(1e-6);
-                        } else {
-                            amp = 1.0;
-                        }
+                        let amp = grain_window(self.window_type, env_pos, g.length);
 
                         let pos_int = g.start_sample as usize;
                         let frac = g.start_sample - pos_int as f32;
+                        let s = read_buffer_interpolated(&self.audio_buffer, pos_int, frac, hq);
 
-                        let s = if pos_int < self.audio_buffer.len() - 1 {
-                            let s1 = self.audio_buffer[pos_int];
-                            let s2 = self.audio_buffer[pos_int + 1];
-                            s1 + (s2 - s1) * frac
-                        } else {
-                            0.0
-                        };
-                        
                         current_sample += s * amp;
 
-                        g.start_sample += g.rate;
+                        g.start_sample += g.pitch_rate;
                         g.age += 1.0;
                         g.age >= g.length
                     };
@@ -245,34 +431,140 @@ impl GranularEngine {
             }
 
             // 2. Filter (Post-granulator)
-            let filtered = self.filter.process(current_sample);
-            
+            let filtered = self.filter_l.process(current_sample);
+
             // 3. Delay
-            let delayed_sig = self.delay.read(delay_time);
+            let delayed_sig = self.delay_l.read(delay_time);
             let delay_in = filtered + (delayed_sig * delay_fb);
-            self.delay.write(delay_in);
-            
+            self.delay_l.write(delay_in);
+
             let delay_out = filtered * (1.0 - delay_mix) + delayed_sig * delay_mix;
-            
-            // 4. Reverb
-            let reverb_out = self.reverb.process(delay_out);
-            
+
+            // 4. Reverb (Dattorro plate; mono tap until the stereo path lands)
+            let reverb_out = self.reverb.process_mono(delay_out);
+
             // 5. Master Gain
             output[i] = reverb_out * master_gain;
         }
     }
 
     fn spawn_grain(&mut self) {
-        let rand_val = self.rng.next_f32(); 
+        let rand_val = self.rng.next_f32();
         let rand_offset = (rand_val * 2.0 - 1.0) * (self.random_start_ms / 1000.0) * self.sample_rate;
-        
-        let mut start = self.region_start as f32 + rand_offset;
+
+        let mut start = self.scan_position + rand_offset;
         start = start.max(self.region_start as f32).min((self.region_end - 1) as f32);
 
         let length = (self.grain_size_ms / 1000.0 * self.sample_rate).max(1.0);
-        let rate = 2.0f32.powf(self.pitch_semitones / 12.0);
+        let pitch_rate = 2.0f32.powf((self.pitch_semitones + self.lfo_pitch_mod) / 12.0);
+        let pan = (self.rng.next_f32() * 2.0 - 1.0) * self.stereo_width;
 
-        self.grains.push(Grain::new(start, length, rate));
+        self.grains.push(Grain::new(start, length, pitch_rate, pan));
+    }
+
+    // Advances the scan (time-stretch) read pointer by scan_rate samples, wrapping
+    // within the active region so grain spawn points keep traversing the buffer
+    // independently of each grain's own pitch_rate.
+    fn advance_scan_position(&mut self) {
+        if self.region_end <= self.region_start {
+            return;
+        }
+        self.scan_position += self.scan_rate;
+        let region_len = (self.region_end - self.region_start) as f32;
+        let region_start = self.region_start as f32;
+        self.scan_position = region_start + (self.scan_position - region_start).rem_euclid(region_len);
+    }
+
+    fn process_stereo_internal(&mut self, left_ptr: *mut f32, right_ptr: *mut f32, len: usize) {
+        let left = unsafe { std::slice::from_raw_parts_mut(left_ptr, len) };
+        let right = unsafe { std::slice::from_raw_parts_mut(right_ptr, len) };
+
+        let density = self.density.max(0.1);
+        let interval_samples = self.sample_rate / density;
+
+        let delay_mix = self.delay_mix;
+        let delay_fb = self.delay_feedback;
+        let master_gain = self.master_gain;
+
+        for i in 0..len {
+            let mut sample_l = 0.0;
+            let mut sample_r = 0.0;
+
+            // 0. Modulation: advance every LFO and route its output to its target
+            let (cutoff_mod, delay_mod, pitch_mod) = self.tick_lfos();
+            self.lfo_pitch_mod = pitch_mod;
+
+            let modulated_cutoff = self.base_cutoff * 2f32.powf(cutoff_mod);
+            self.filter_l.set_params(self.filter_type, modulated_cutoff, self.base_q, self.base_filter_gain);
+            self.filter_r.set_params(self.filter_type, modulated_cutoff, self.base_q, self.base_filter_gain);
+            let delay_time = (self.delay_time_ms + delay_mod).max(0.0);
+
+            // 1. Granular Generation
+            if !self.audio_buffer.is_empty() && self.is_playing {
+                self.time_since_last_grain += 1.0;
+                if self.time_since_last_grain >= interval_samples {
+                    self.spawn_grain();
+                    self.time_since_last_grain -= interval_samples;
+                }
+                self.advance_scan_position();
+            }
+
+            if !self.grains.is_empty() {
+                let hq = self.hq_interpolation;
+                let mut j = 0;
+                while j < self.grains.len() {
+                    let remove = {
+                        let g = &mut self.grains[j];
+
+                        let env_pos = g.age / g.length;
+                        let amp = grain_window(self.window_type, env_pos, g.length);
+
+                        let pos_int = g.start_sample as usize;
+                        let frac = g.start_sample - pos_int as f32;
+                        let s = read_buffer_interpolated(&self.audio_buffer, pos_int, frac, hq);
+
+                        let (gain_l, gain_r) = equal_power_gains(g.pan);
+                        sample_l += s * amp * gain_l;
+                        sample_r += s * amp * gain_r;
+
+                        g.start_sample += g.pitch_rate;
+                        g.age += 1.0;
+                        g.age >= g.length
+                    };
+
+                    if remove {
+                        self.grains.swap_remove(j);
+                    } else {
+                        j += 1;
+                    }
+                }
+            }
+
+            // 2. Filter (Post-granulator), per channel
+            let filtered_l = self.filter_l.process(sample_l);
+            let filtered_r = self.filter_r.process(sample_r);
+
+            // 3. Delay, per channel
+            let delayed_l = self.delay_l.read(delay_time);
+            let delay_in_l = filtered_l + (delayed_l * delay_fb);
+            self.delay_l.write(delay_in_l);
+            let delay_out_l = filtered_l * (1.0 - delay_mix) + delayed_l * delay_mix;
+
+            let delayed_r = self.delay_r.read(delay_time);
+            let delay_in_r = filtered_r + (delayed_r * delay_fb);
+            self.delay_r.write(delay_in_r);
+            let delay_out_r = filtered_r * (1.0 - delay_mix) + delayed_r * delay_mix;
+
+            // 4. Reverb: fed the mono sum (the tank is naturally mono-in/stereo-out),
+            // then its blended output replaces the shared mono reference in each
+            // channel so the dry stereo width from panning/delay is preserved.
+            let mono_in = (delay_out_l + delay_out_r) * 0.5;
+            let (reverb_l, reverb_r) = self.reverb.process(mono_in);
+
+            // 5. Master Gain
+            left[i] = (delay_out_l + reverb_l - mono_in) * master_gain;
+            right[i] = (delay_out_r + reverb_r - mono_in) * master_gain;
+        }
     }
 }
 
@@ -289,22 +581,34 @@ pub fn granularengine_set_region(engine: &mut GranularEngine, start: usize, end:
 }
 
 #[wasm_bindgen]
-pub fn granularengine_set_params(engine: &mut GranularEngine, grain_size_ms: f32, density: f32, random_start_ms: f32, pitch_semitones: f32) {
-    engine.set_params_internal(grain_size_ms, density, random_start_ms, pitch_semitones);
+pub fn granularengine_set_params(engine: &mut GranularEngine, grain_size_ms: f32, density: f32, random_start_ms: f32, pitch_semitones: f32, stereo_width: f32, scan_rate: f32, hq_interpolation: bool) {
+    engine.set_params_internal(grain_size_ms, density, random_start_ms, pitch_semitones, stereo_width, scan_rate, hq_interpolation);
+}
+
+#[wasm_bindgen]
+pub fn granularengine_set_window_type(engine: &mut GranularEngine, window_type: u32) {
+    engine.set_window_type_internal(window_type);
 }
 
 #[wasm_bindgen]
 pub fn granularengine_set_effect_params(
-    engine: &mut GranularEngine, 
-    cutoff: f32, 
-    q: f32, 
-    delay_time_ms: f32, 
-    delay_feedback: f32, 
+    engine: &mut GranularEngine,
+    filter_type: u32,
+    cutoff: f32,
+    q: f32,
+    filter_gain: f32,
+    delay_time_ms: f32,
+    delay_feedback: f32,
     delay_mix: f32,
     reverb_mix: f32,
     master_gain: f32
 ) {
-    engine.set_effect_params_internal(cutoff, q, delay_time_ms, delay_feedback, delay_mix, reverb_mix, master_gain);
+    engine.set_effect_params_internal(filter_type, cutoff, q, filter_gain, delay_time_ms, delay_feedback, delay_mix, reverb_mix, master_gain);
+}
+
+#[wasm_bindgen]
+pub fn granularengine_set_lfo(engine: &mut GranularEngine, index: usize, target: u32, shape: u32, rate_hz: f32, depth: f32) {
+    engine.set_lfo_internal(index, target, shape, rate_hz, depth);
 }
 
 #[wasm_bindgen]
@@ -316,3 +620,8 @@ pub fn granularengine_set_playing(engine: &mut GranularEngine, playing: bool) {
 pub fn granularengine_process(engine: &mut GranularEngine, output_ptr: *mut f32, len: usize) {
     engine.process_internal(output_ptr, len);
 }
+
+#[wasm_bindgen]
+pub fn granularengine_process_stereo(engine: &mut GranularEngine, left_ptr: *mut f32, right_ptr: *mut f32, len: usize) {
+    engine.process_stereo_internal(left_ptr, right_ptr, len);
+}